@@ -1,7 +1,23 @@
+use chrono::Utc;
+
+use crate::timestamp::Timestamp;
+
 #[derive(Debug, serde::Serialize, sqlx::FromRow)]
 pub struct Booking {
     pub id: i64,
-    pub startdate: i64,
-    pub enddate: Option<i64>,
+    pub startdate: Timestamp,
+    pub enddate: Option<Timestamp>,
     pub des: String,
+    #[sqlx(default)]
+    pub duration_ms: i64,
+}
+
+impl Booking {
+    /// Fills in the derived `duration_ms` field so clients don't have to subtract timestamps
+    /// themselves: the time between `startdate` and `enddate`, or now for open bookings.
+    pub fn with_duration(mut self) -> Self {
+        let end = self.enddate.map_or_else(Utc::now, |timestamp| timestamp.0);
+        self.duration_ms = (end - self.startdate.0).num_milliseconds();
+        self
+    }
 }
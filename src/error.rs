@@ -0,0 +1,36 @@
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::IntoResponse;
+use uuid::Uuid;
+
+pub struct AppError(anyhow::Error);
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> axum::response::Response {
+        let request_id = Uuid::new_v4();
+        tracing::error!(error = %self.0, %request_id, "request failed");
+        crate::metrics::Metrics::global().db_errors_total.inc();
+
+        let mut response = (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Something went wrong: {}", self.0),
+        )
+            .into_response();
+
+        response.headers_mut().insert(
+            "x-request-id",
+            HeaderValue::from_str(&request_id.to_string())
+                .unwrap_or_else(|_| HeaderValue::from_static("invalid")),
+        );
+
+        response
+    }
+}
+
+impl<E> From<E> for AppError
+where
+    E: Into<anyhow::Error>,
+{
+    fn from(err: E) -> Self {
+        Self(err.into())
+    }
+}
@@ -0,0 +1,216 @@
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use axum_extra::extract::Query;
+use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Sqlite};
+
+use crate::error::AppError;
+use crate::timestamp::Timestamp;
+
+use super::ApiContext;
+
+#[derive(Deserialize, Debug)]
+pub struct AnalyticsGetQueryParams {
+    startdate_min: Option<Timestamp>,
+    startdate_max: Option<Timestamp>,
+    enddate_min: Option<Timestamp>,
+    enddate_max: Option<Timestamp>,
+    tag: Option<Vec<String>>,
+    description_contains: Option<String>,
+    bucket: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TagAnalytics {
+    tag_id: i64,
+    tag_name: String,
+    total_ms: i64,
+    booking_count: i64,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct BucketAnalytics {
+    bucket: String,
+    total_ms: i64,
+    booking_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnalyticsResponse {
+    tags: Vec<TagAnalytics>,
+    untagged_total_ms: i64,
+    grand_total_ms: i64,
+    buckets: Option<Vec<BucketAnalytics>>,
+}
+
+// Pushes the filters shared by get_bookings onto a query that already has a `booking` row
+// aliased as `b` and, for tag-aware queries, a `tag` row aliased as `t`. Matches get_bookings'
+// filter semantics exactly: bounds are inclusive, and enddate_min/enddate_max filter the raw
+// `enddate` column, so running bookings (enddate IS NULL) never match either bound.
+fn push_common_filters<'a>(
+    query_builder: &mut QueryBuilder<'a, Sqlite>,
+    params: &'a AnalyticsGetQueryParams,
+    with_tag_join: bool,
+) {
+    query_builder.push(" WHERE TRUE");
+
+    if let Some(startdate_min) = params.startdate_min {
+        query_builder
+            .push(" AND b.startdate >= ")
+            .push_bind(startdate_min);
+    }
+
+    if let Some(startdate_max) = params.startdate_max {
+        query_builder
+            .push(" AND b.startdate <= ")
+            .push_bind(startdate_max);
+    }
+
+    if let Some(enddate_min) = params.enddate_min {
+        query_builder
+            .push(" AND b.enddate >= ")
+            .push_bind(enddate_min);
+    }
+
+    if let Some(enddate_max) = params.enddate_max {
+        query_builder
+            .push(" AND b.enddate <= ")
+            .push_bind(enddate_max);
+    }
+
+    if let Some(description_contains) = &params.description_contains {
+        query_builder
+            .push(" AND b.des LIKE '%' || ")
+            .push_bind(description_contains)
+            .push(" || '%'");
+    }
+
+    if with_tag_join {
+        if let Some(tags) = &params.tag {
+            query_builder.push(" AND t.name IN (");
+            let tags_len = tags.len();
+            for (index, tag) in tags.iter().enumerate() {
+                query_builder.push_bind(tag);
+                if index < tags_len - 1 {
+                    query_builder.push(",");
+                }
+            }
+            query_builder.push(")");
+        }
+    }
+}
+
+fn bucket_expr(bucket: &str) -> Option<&'static str> {
+    match bucket {
+        "day" => Some("strftime('%Y-%m-%d', datetime(b.startdate / 1000, 'unixepoch'))"),
+        "week" => Some("strftime('%Y-W%W', datetime(b.startdate / 1000, 'unixepoch'))"),
+        "month" => Some("strftime('%Y-%m', datetime(b.startdate / 1000, 'unixepoch'))"),
+        _ => None,
+    }
+}
+
+/// Reports time tracked per tag over an optional date range.
+///
+/// A booking with N tags contributes its duration to each of those N tag buckets, so the
+/// per-tag totals can exceed wall-clock time. `grand_total_ms` is instead computed over
+/// `DISTINCT` bookings so it never double-counts multi-tagged bookings, and
+/// `untagged_total_ms` covers bookings that have no row in `tagassignment` at all.
+#[tracing::instrument(skip(ctx))]
+pub async fn get_analytics(
+    ctx: Extension<ApiContext>,
+    Query(params): Query<AnalyticsGetQueryParams>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Some(bucket) = &params.bucket {
+        if bucket_expr(bucket).is_none() {
+            return Ok((
+                StatusCode::BAD_REQUEST,
+                "bucket must be one of: day, week, month",
+            )
+                .into_response());
+        }
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+
+    let mut tags_query: QueryBuilder<Sqlite> = QueryBuilder::new(
+        "SELECT t.id AS tag_id, t.name AS tag_name, \
+         SUM(COALESCE(b.enddate, ",
+    );
+    tags_query
+        .push_bind(now)
+        .push(") - b.startdate) AS total_ms, COUNT(*) AS booking_count \
+         FROM booking b \
+         INNER JOIN tagassignment tg ON b.id = tg.bid \
+         INNER JOIN tag t ON t.id = tg.tgid");
+    push_common_filters(&mut tags_query, &params, true);
+    tags_query.push(" GROUP BY t.id, t.name");
+
+    let tags = tags_query
+        .build_query_as::<TagAnalytics>()
+        .fetch_all(&ctx.pool)
+        .await?;
+
+    let mut untagged_query: QueryBuilder<Sqlite> = QueryBuilder::new(
+        "SELECT COALESCE(SUM(COALESCE(b.enddate, ",
+    );
+    untagged_query
+        .push_bind(now)
+        .push(") - b.startdate), 0) FROM booking b \
+         LEFT JOIN tagassignment tg ON b.id = tg.bid");
+    push_common_filters(&mut untagged_query, &params, false);
+    untagged_query.push(" AND tg.bid IS NULL");
+
+    let untagged_total_ms: i64 = untagged_query.build_query_scalar().fetch_one(&ctx.pool).await?;
+
+    let mut grand_total_query: QueryBuilder<Sqlite> = QueryBuilder::new(
+        "SELECT COALESCE(SUM(duration), 0) FROM (SELECT DISTINCT b.id, COALESCE(b.enddate, ",
+    );
+    grand_total_query
+        .push_bind(now)
+        .push(") - b.startdate AS duration FROM booking b \
+         LEFT JOIN tagassignment tg ON b.id = tg.bid \
+         LEFT JOIN tag t ON t.id = tg.tgid");
+    push_common_filters(&mut grand_total_query, &params, true);
+    grand_total_query.push(")");
+
+    let grand_total_ms: i64 = grand_total_query
+        .build_query_scalar()
+        .fetch_one(&ctx.pool)
+        .await?;
+
+    let buckets = if let Some(bucket) = &params.bucket {
+        let expr = bucket_expr(bucket).unwrap();
+        let mut bucket_query: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+            "SELECT {expr} AS bucket, COALESCE(SUM(duration), 0) AS total_ms, COUNT(*) AS booking_count FROM \
+             (SELECT DISTINCT b.id, b.startdate, COALESCE(b.enddate, "
+        ));
+        bucket_query
+            .push_bind(now)
+            .push(") - b.startdate AS duration FROM booking b \
+             LEFT JOIN tagassignment tg ON b.id = tg.bid \
+             LEFT JOIN tag t ON t.id = tg.tgid");
+        push_common_filters(&mut bucket_query, &params, true);
+        bucket_query.push(format!(") b GROUP BY {expr} ORDER BY bucket"));
+
+        Some(
+            bucket_query
+                .build_query_as::<BucketAnalytics>()
+                .fetch_all(&ctx.pool)
+                .await?,
+        )
+    } else {
+        None
+    };
+
+    Ok(Json(AnalyticsResponse {
+        tags,
+        untagged_total_ms,
+        grand_total_ms,
+        buckets,
+    })
+    .into_response())
+}
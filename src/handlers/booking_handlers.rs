@@ -1,12 +1,14 @@
+use axum::extract::Path;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::{Extension, Json};
 use axum_extra::extract::Query;
-use serde::Deserialize;
-use sqlx::{QueryBuilder, Sqlite};
+use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection};
 
 use crate::booking::Booking;
 use crate::error::AppError;
+use crate::timestamp::Timestamp;
 
 use super::ApiContext;
 
@@ -15,6 +17,7 @@ pub struct BookingDeleteQueryParams {
     id: i64,
 }
 
+#[tracing::instrument(skip(ctx))]
 pub async fn delete_booking(
     ctx: Extension<ApiContext>,
     Query(params): Query<BookingDeleteQueryParams>,
@@ -28,11 +31,12 @@ pub async fn delete_booking(
 #[derive(Deserialize, Debug)]
 pub struct BookingPatchQueryParams {
     id: i64,
-    startdate: Option<i64>,
-    enddate: Option<i64>,
+    startdate: Option<Timestamp>,
+    enddate: Option<Timestamp>,
     description: Option<String>,
 }
 
+#[tracing::instrument(skip(ctx))]
 pub async fn patch_booking(
     ctx: Extension<ApiContext>,
     Query(params): Query<BookingPatchQueryParams>,
@@ -75,26 +79,42 @@ pub async fn patch_booking(
         .fetch_one(&ctx.pool)
         .await?;
 
-    Ok(Json(booking).into_response())
+    Ok(Json(booking.with_duration()).into_response())
 }
 
 #[derive(Deserialize, Debug)]
 pub struct BookingPostQueryParams {
-    enddate: Option<i64>,
+    enddate: Option<Timestamp>,
     description: Option<String>,
 }
 
+#[tracing::instrument(skip(ctx))]
 pub async fn post_booking(
     ctx: Extension<ApiContext>,
     Query(params): Query<BookingPostQueryParams>,
 ) -> Result<impl IntoResponse, AppError> {
+    let startdate = Timestamp::now();
+    let now_ms = startdate.0.timestamp_millis();
+    let end_ms = params.enddate.map_or(now_ms, |enddate| enddate.0.timestamp_millis());
+
+    let mut tx = ctx.pool.begin().await?;
+
+    if ctx.single_active {
+        let conflicts = find_overlapping_booking_ids(&mut tx, now_ms, end_ms, now_ms).await?;
+        if !conflicts.is_empty() {
+            return Ok((
+                StatusCode::CONFLICT,
+                Json(OverlapConflict {
+                    message: "booking overlaps with an existing booking".to_string(),
+                    conflicting_booking_ids: conflicts,
+                }),
+            )
+                .into_response());
+        }
+    }
+
     let mut query_builder: QueryBuilder<Sqlite> =
         QueryBuilder::new("INSERT INTO BOOKING (startdate");
-    let time = std::time::SystemTime::now();
-    let startdate = time
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_millis() as i64;
 
     if params.enddate.is_some() {
         query_builder.push(", enddate");
@@ -118,23 +138,28 @@ pub async fn post_booking(
 
     let booking = query_builder
         .build_query_as::<Booking>()
-        .fetch_one(&ctx.pool)
+        .fetch_one(&mut *tx)
         .await?;
 
-    Ok((StatusCode::CREATED, Json(booking)).into_response())
+    tx.commit().await?;
+
+    ctx.metrics.bookings_created_total.inc();
+
+    Ok((StatusCode::CREATED, Json(booking.with_duration())).into_response())
 }
 
 #[derive(Deserialize, Debug)]
 pub struct BookingGetQueryParams {
     id: Option<i64>,
-    startdate_min: Option<i64>,
-    startdate_max: Option<i64>,
-    enddate_min: Option<i64>,
-    enddate_max: Option<i64>,
+    startdate_min: Option<Timestamp>,
+    startdate_max: Option<Timestamp>,
+    enddate_min: Option<Timestamp>,
+    enddate_max: Option<Timestamp>,
     tag: Option<Vec<String>>,
     description_contains: Option<String>,
 }
 
+#[tracing::instrument(skip(ctx))]
 pub async fn get_bookings(
     ctx: Extension<ApiContext>,
     Query(params): Query<BookingGetQueryParams>,
@@ -158,29 +183,33 @@ pub async fn get_bookings(
 
     if let Some(startdate_min) = params.startdate_min {
         query_builder
-            .push(" AND startdate > ")
+            .push(" AND startdate >= ")
             .push_bind(startdate_min);
     }
 
     if let Some(startdate_max) = params.startdate_max {
         query_builder
-            .push(" AND startdate < ")
+            .push(" AND startdate <= ")
             .push_bind(startdate_max);
     }
 
     if let Some(enddate_min) = params.enddate_min {
-        query_builder.push(" AND enddate > ").push_bind(enddate_min);
+        query_builder
+            .push(" AND enddate >= ")
+            .push_bind(enddate_min);
     }
 
     if let Some(enddate_max) = params.enddate_max {
-        query_builder.push(" AND enddate > ").push_bind(enddate_max);
+        query_builder
+            .push(" AND enddate <= ")
+            .push_bind(enddate_max);
     }
 
     if let Some(description_contains) = params.description_contains {
         query_builder
-            .push(" AND des LIKE CONCAT('%', ")
+            .push(" AND des LIKE '%' || ")
             .push_bind(description_contains)
-            .push(", '%')");
+            .push(" || '%'");
     }
 
     if let Some(tags) = params.tag {
@@ -197,5 +226,253 @@ pub async fn get_bookings(
 
     let bookings: Vec<Booking> = query_builder.build_query_as().fetch_all(&ctx.pool).await?;
 
-    Ok(Json(bookings))
+    Ok(Json(
+        bookings.into_iter().map(Booking::with_duration).collect::<Vec<_>>(),
+    ))
+}
+
+#[tracing::instrument(skip(ctx))]
+pub async fn get_active_bookings(ctx: Extension<ApiContext>) -> Result<impl IntoResponse, AppError> {
+    let bookings: Vec<Booking> =
+        sqlx::query_as("SELECT * FROM booking WHERE enddate IS NULL")
+            .fetch_all(&ctx.pool)
+            .await?;
+
+    Ok(Json(
+        bookings.into_iter().map(Booking::with_duration).collect::<Vec<_>>(),
+    ))
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(test, derive(Deserialize))]
+pub struct OverlapConflict {
+    message: String,
+    conflicting_booking_ids: Vec<i64>,
+}
+
+/// Finds bookings whose `[startdate, COALESCE(enddate, now)]` interval intersects `[start, end]`.
+async fn find_overlapping_booking_ids(
+    conn: &mut SqliteConnection,
+    start: i64,
+    end: i64,
+    now: i64,
+) -> Result<Vec<i64>, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT id FROM booking WHERE startdate <= $1 AND COALESCE(enddate, $3) >= $2",
+        end,
+        start,
+        now
+    )
+    .fetch_all(conn)
+    .await
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BookingStartQueryParams {
+    description: Option<String>,
+    tag_id: Option<Vec<i64>>,
+}
+
+/// Starts a new running timer: a booking with `startdate = now` and `enddate = NULL`.
+///
+/// When `ApiContext::single_active` is enabled, this rejects the request with 409 if another
+/// booking is already open, rather than silently running two timers at once.
+#[tracing::instrument(skip(ctx))]
+pub async fn start_booking(
+    ctx: Extension<ApiContext>,
+    Query(params): Query<BookingStartQueryParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let now = Timestamp::now();
+    let now_ms = now.0.timestamp_millis();
+
+    let mut tx = ctx.pool.begin().await?;
+
+    if ctx.single_active {
+        let conflicts = find_overlapping_booking_ids(&mut tx, now_ms, now_ms, now_ms).await?;
+        if !conflicts.is_empty() {
+            return Ok((
+                StatusCode::CONFLICT,
+                Json(OverlapConflict {
+                    message: "another booking is already running".to_string(),
+                    conflicting_booking_ids: conflicts,
+                }),
+            )
+                .into_response());
+        }
+    }
+
+    let mut query_builder: QueryBuilder<Sqlite> =
+        QueryBuilder::new("INSERT INTO booking (startdate");
+
+    if params.description.is_some() {
+        query_builder.push(", des");
+    }
+
+    query_builder.push(") VALUES (").push_bind(now);
+
+    if let Some(description) = &params.description {
+        query_builder.push(", ").push_bind(description);
+    }
+
+    query_builder.push(") RETURNING id, startdate, enddate, des");
+
+    let booking = query_builder
+        .build_query_as::<Booking>()
+        .fetch_one(&mut *tx)
+        .await?;
+
+    if let Some(tag_ids) = params.tag_id {
+        let mut tagassignment_query: QueryBuilder<Sqlite> =
+            QueryBuilder::new("INSERT INTO tagassignment (tgid, bid) VALUES");
+        let tag_ids_len = tag_ids.len();
+        for (index, tag_id) in tag_ids.into_iter().enumerate() {
+            tagassignment_query
+                .push(" (")
+                .push_bind(tag_id)
+                .push(", ")
+                .push_bind(booking.id)
+                .push(")");
+            if index < tag_ids_len - 1 {
+                tagassignment_query.push(",");
+            }
+        }
+        tagassignment_query.build().execute(&mut *tx).await?;
+    }
+
+    tx.commit().await?;
+
+    ctx.metrics.bookings_created_total.inc();
+
+    Ok((StatusCode::CREATED, Json(booking.with_duration())).into_response())
+}
+
+/// Stops the given booking's running timer, setting `enddate = now`. Returns 409 if the
+/// booking isn't currently open.
+#[tracing::instrument(skip(ctx))]
+pub async fn stop_booking(
+    ctx: Extension<ApiContext>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let now = Timestamp::now();
+
+    let mut tx = ctx.pool.begin().await?;
+
+    let booking = sqlx::query_as::<_, Booking>(
+        "UPDATE booking SET enddate = $1 WHERE id = $2 AND enddate IS NULL \
+         RETURNING id, startdate, enddate, des",
+    )
+    .bind(now)
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let Some(booking) = booking else {
+        return Ok((
+            StatusCode::CONFLICT,
+            format!("booking {id} is not currently running"),
+        )
+            .into_response());
+    };
+
+    tx.commit().await?;
+
+    Ok(Json(booking.with_duration()).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use crate::metrics::Metrics;
+
+    use super::*;
+
+    async fn test_ctx(single_active: bool) -> ApiContext {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::query(
+            "CREATE TABLE booking ( \
+                 id INTEGER PRIMARY KEY AUTOINCREMENT, \
+                 startdate INTEGER NOT NULL, \
+                 enddate INTEGER, \
+                 des TEXT NOT NULL DEFAULT '' \
+             )",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        ApiContext {
+            pool,
+            metrics: Metrics::global().clone(),
+            single_active,
+        }
+    }
+
+    #[tokio::test]
+    async fn starting_a_booking_while_one_is_open_returns_409_with_conflicting_id() {
+        let ctx = test_ctx(true).await;
+
+        let first = start_booking(
+            Extension(ctx.clone()),
+            Query(BookingStartQueryParams {
+                description: None,
+                tag_id: None,
+            }),
+        )
+        .await
+        .unwrap()
+        .into_response();
+        assert_eq!(first.status(), StatusCode::CREATED);
+
+        let second = start_booking(
+            Extension(ctx.clone()),
+            Query(BookingStartQueryParams {
+                description: None,
+                tag_id: None,
+            }),
+        )
+        .await
+        .unwrap()
+        .into_response();
+        assert_eq!(second.status(), StatusCode::CONFLICT);
+
+        let body = to_bytes(second.into_body(), usize::MAX).await.unwrap();
+        let conflict: OverlapConflict = serde_json::from_slice(&body).unwrap();
+        assert_eq!(conflict.conflicting_booking_ids, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn stopping_an_already_stopped_booking_returns_409() {
+        let ctx = test_ctx(false).await;
+
+        let started = start_booking(
+            Extension(ctx.clone()),
+            Query(BookingStartQueryParams {
+                description: None,
+                tag_id: None,
+            }),
+        )
+        .await
+        .unwrap()
+        .into_response();
+        assert_eq!(started.status(), StatusCode::CREATED);
+
+        let first_stop = stop_booking(Extension(ctx.clone()), Path(1))
+            .await
+            .unwrap()
+            .into_response();
+        assert_eq!(first_stop.status(), StatusCode::OK);
+
+        let second_stop = stop_booking(Extension(ctx.clone()), Path(1))
+            .await
+            .unwrap()
+            .into_response();
+        assert_eq!(second_stop.status(), StatusCode::CONFLICT);
+    }
 }
@@ -0,0 +1,19 @@
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::Extension;
+
+use super::ApiContext;
+
+/// Serves the process-wide registry in Prometheus text exposition format.
+pub async fn get_metrics(ctx: Extension<ApiContext>) -> impl IntoResponse {
+    let idle = ctx.pool.num_idle() as i64;
+    let total = ctx.pool.size() as i64;
+
+    ctx.metrics.pool_idle_connections.set(idle);
+    ctx.metrics.pool_active_connections.set((total - idle).max(0));
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        ctx.metrics.encode(),
+    )
+}
@@ -1,19 +1,65 @@
-use axum::routing::get;
-use axum::Router;
+use std::time::Instant;
+
+use axum::extract::Request;
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::routing::{get, post};
+use axum::{Extension, Router};
 use sqlx::SqlitePool;
 use tower::ServiceBuilder;
 use tower_http::add_extension::AddExtensionLayer;
+use tower_http::trace::TraceLayer;
+
+use crate::metrics::Metrics;
 
+mod analytics_handlers;
 mod booking_handlers;
+mod metrics_handlers;
+mod schedule_handlers;
 mod tag_handlers;
 mod tagassignment_handlers;
 
 #[derive(Clone)]
 struct ApiContext {
     pool: SqlitePool,
+    metrics: Metrics,
+    /// When enabled, starting or creating a booking that overlaps an already-open booking is
+    /// rejected with 409 instead of allowing multiple timers to run at once.
+    single_active: bool,
 }
 
-pub async fn router(pool: SqlitePool) -> Router {
+async fn track_metrics(ctx: Extension<ApiContext>, req: Request, next: Next) -> Response {
+    let method = req.method().to_string();
+    let path = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map(|matched| matched.as_str().to_owned())
+        .unwrap_or_else(|| req.uri().path().to_owned());
+
+    let start = Instant::now();
+    let response = next.run(req).await;
+    let latency = start.elapsed().as_secs_f64();
+    let status = response.status().as_u16().to_string();
+
+    ctx.metrics
+        .http_requests_total
+        .with_label_values(&[&method, &path, &status])
+        .inc();
+    ctx.metrics
+        .http_request_duration_seconds
+        .with_label_values(&[&method, &path])
+        .observe(latency);
+
+    response
+}
+
+pub async fn router(pool: SqlitePool, single_active: bool) -> Router {
+    let ctx = ApiContext {
+        pool,
+        metrics: Metrics::global().clone(),
+        single_active,
+    };
+
     Router::new()
         .route(
             "/api/bookings",
@@ -22,6 +68,15 @@ pub async fn router(pool: SqlitePool) -> Router {
                 .patch(booking_handlers::patch_booking)
                 .delete(booking_handlers::delete_booking),
         )
+        .route("/api/bookings/start", post(booking_handlers::start_booking))
+        .route(
+            "/api/bookings/active",
+            get(booking_handlers::get_active_bookings),
+        )
+        .route(
+            "/api/bookings/:id/stop",
+            post(booking_handlers::stop_booking),
+        )
         .route(
             "/api/tags",
             get(tag_handlers::get_tags)
@@ -35,5 +90,23 @@ pub async fn router(pool: SqlitePool) -> Router {
                 .post(tagassignment_handlers::post_tagassignments)
                 .delete(tagassignment_handlers::delete_tagassignment),
         )
-        .layer(ServiceBuilder::new().layer(AddExtensionLayer::new(ApiContext { pool })))
+        .route("/api/analytics", get(analytics_handlers::get_analytics))
+        .route(
+            "/api/schedules",
+            get(schedule_handlers::get_schedules)
+                .post(schedule_handlers::post_schedule)
+                .patch(schedule_handlers::patch_schedule)
+                .delete(schedule_handlers::delete_schedule),
+        )
+        .route(
+            "/api/schedules/:id/materialize",
+            post(schedule_handlers::materialize_schedule),
+        )
+        .route("/metrics", get(metrics_handlers::get_metrics))
+        .layer(
+            ServiceBuilder::new()
+                .layer(TraceLayer::new_for_http())
+                .layer(AddExtensionLayer::new(ctx))
+                .layer(middleware::from_fn(track_metrics)),
+        )
 }
@@ -0,0 +1,225 @@
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use axum_extra::extract::Query;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::{QueryBuilder, Sqlite};
+
+use crate::booking::Booking;
+use crate::error::AppError;
+use crate::period::{parse_period, Periods};
+use crate::schedule::Schedule;
+
+use super::ApiContext;
+
+#[derive(Deserialize, Debug)]
+pub struct ScheduleGetQueryParams {
+    id: Option<i64>,
+    name: Option<String>,
+}
+
+#[tracing::instrument(skip(ctx))]
+pub async fn get_schedules(
+    ctx: Extension<ApiContext>,
+    Query(params): Query<ScheduleGetQueryParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut query_builder: QueryBuilder<Sqlite> =
+        QueryBuilder::new("SELECT * FROM schedule WHERE TRUE");
+
+    if let Some(id) = params.id {
+        query_builder.push(" AND id = ").push_bind(id);
+    }
+
+    if let Some(name) = params.name {
+        query_builder.push(" AND name = ").push_bind(name);
+    }
+
+    let schedules = query_builder
+        .build_query_as::<Schedule>()
+        .fetch_all(&ctx.pool)
+        .await?;
+
+    Ok(Json(schedules))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SchedulePostQueryParams {
+    name: String,
+    period: Vec<String>,
+}
+
+#[tracing::instrument(skip(ctx))]
+pub async fn post_schedule(
+    ctx: Extension<ApiContext>,
+    Query(params): Query<SchedulePostQueryParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let periods = match parse_periods(&params.period) {
+        Ok(periods) => periods,
+        Err(message) => return Ok((StatusCode::BAD_REQUEST, message).into_response()),
+    };
+
+    let schedule = sqlx::query_as::<_, Schedule>(
+        "INSERT INTO schedule (name, periods) VALUES ($1, $2) RETURNING id, name, periods",
+    )
+    .bind(params.name)
+    .bind(periods)
+    .fetch_one(&ctx.pool)
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(schedule)).into_response())
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SchedulePatchQueryParams {
+    id: i64,
+    name: Option<String>,
+    period: Option<Vec<String>>,
+}
+
+#[tracing::instrument(skip(ctx))]
+pub async fn patch_schedule(
+    ctx: Extension<ApiContext>,
+    Query(params): Query<SchedulePatchQueryParams>,
+) -> Result<impl IntoResponse, AppError> {
+    if params.name.is_none() && params.period.is_none() {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
+
+    let periods = match params.period.as_deref().map(parse_periods) {
+        Some(Ok(periods)) => Some(periods),
+        Some(Err(message)) => return Ok((StatusCode::BAD_REQUEST, message).into_response()),
+        None => None,
+    };
+
+    let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new("UPDATE schedule SET ");
+    let mut comma_necessary = false;
+
+    if let Some(name) = params.name {
+        query_builder.push("name = ").push_bind(name);
+        comma_necessary = true;
+    }
+
+    if let Some(periods) = periods {
+        if comma_necessary {
+            query_builder.push(", ");
+        }
+        query_builder.push("periods = ").push_bind(periods);
+    }
+
+    query_builder
+        .push(" WHERE id = ")
+        .push_bind(params.id)
+        .push(" RETURNING id, name, periods");
+
+    let schedule = query_builder
+        .build_query_as::<Schedule>()
+        .fetch_one(&ctx.pool)
+        .await?;
+
+    Ok(Json(schedule).into_response())
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ScheduleDeleteQueryParams {
+    id: i64,
+}
+
+#[tracing::instrument(skip(ctx))]
+pub async fn delete_schedule(
+    ctx: Extension<ApiContext>,
+    Query(params): Query<ScheduleDeleteQueryParams>,
+) -> Result<impl IntoResponse, AppError> {
+    sqlx::query!("DELETE FROM schedule WHERE id = $1", params.id)
+        .execute(&ctx.pool)
+        .await?;
+    Ok(())
+}
+
+fn parse_periods(raw: &[String]) -> Result<Periods, String> {
+    let periods = raw
+        .iter()
+        .map(|raw| parse_period(raw))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(overnight) = periods.iter().find(|period| period.is_overnight()) {
+        return Err(format!(
+            "overnight periods are not supported: {:?}-{:?}",
+            overnight.start, overnight.end
+        ));
+    }
+
+    Ok(Periods(periods))
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ScheduleMaterializeQueryParams {
+    from: i64,
+    to: i64,
+}
+
+const MS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+/// Expands a schedule's daily periods into concrete `booking` rows over `[from, to)`.
+#[tracing::instrument(skip(ctx))]
+pub async fn materialize_schedule(
+    ctx: Extension<ApiContext>,
+    Path(id): Path<i64>,
+    Query(params): Query<ScheduleMaterializeQueryParams>,
+) -> Result<impl IntoResponse, AppError> {
+    if params.to <= params.from {
+        return Ok((StatusCode::BAD_REQUEST, "to must be after from").into_response());
+    }
+
+    let schedule = sqlx::query_as::<_, Schedule>("SELECT * FROM schedule WHERE id = $1")
+        .bind(id)
+        .fetch_one(&ctx.pool)
+        .await?;
+
+    let mut tx = ctx.pool.begin().await?;
+    let mut bookings = Vec::new();
+
+    let mut day_start_ms = params.from - params.from.rem_euclid(MS_PER_DAY);
+    while day_start_ms < params.to {
+        let day = DateTime::<Utc>::from_timestamp_millis(day_start_ms)
+            .ok_or_else(|| anyhow::anyhow!("out of range day timestamp"))?
+            .date_naive();
+
+        for period in &schedule.periods.0 {
+            if period.is_overnight() {
+                continue;
+            }
+
+            let startdate = day.and_time(period.start).and_utc().timestamp_millis();
+            let enddate = day.and_time(period.end).and_utc().timestamp_millis();
+
+            if startdate < params.from || startdate >= params.to {
+                continue;
+            }
+
+            let booking = sqlx::query_as::<_, Booking>(
+                "INSERT INTO booking (startdate, enddate, des) VALUES ($1, $2, $3) \
+                 RETURNING id, startdate, enddate, des",
+            )
+            .bind(startdate)
+            .bind(enddate)
+            .bind(format!("{} (schedule)", schedule.name))
+            .fetch_one(&mut *tx)
+            .await?;
+
+            bookings.push(booking);
+        }
+
+        day_start_ms += MS_PER_DAY;
+    }
+
+    tx.commit().await?;
+
+    let bookings = bookings
+        .into_iter()
+        .map(Booking::with_duration)
+        .collect::<Vec<_>>();
+
+    Ok((StatusCode::CREATED, Json(bookings)).into_response())
+}
@@ -16,6 +16,7 @@ pub struct TagGetQueryParams {
     name: Option<String>,
 }
 
+#[tracing::instrument(skip(ctx))]
 pub async fn get_tags(
     ctx: Extension<ApiContext>,
     Query(params): Query<TagGetQueryParams>,
@@ -43,6 +44,7 @@ pub struct TagPostQueryParams {
     name: String,
 }
 
+#[tracing::instrument(skip(ctx))]
 pub async fn post_tag(
     ctx: Extension<ApiContext>,
     Query(params): Query<TagPostQueryParams>,
@@ -55,6 +57,8 @@ pub async fn post_tag(
     .fetch_one(&ctx.pool)
     .await?;
 
+    ctx.metrics.tags_created_total.inc();
+
     Ok((StatusCode::CREATED, Json(tag)).into_response())
 }
 
@@ -64,6 +68,7 @@ pub struct TagPatchQueryParams {
     name: Option<String>,
 }
 
+#[tracing::instrument(skip(ctx))]
 pub async fn patch_tag(
     ctx: Extension<ApiContext>,
     Query(params): Query<TagPatchQueryParams>,
@@ -92,6 +97,7 @@ pub struct TagDeleteQueryParams {
     id: i64,
 }
 
+#[tracing::instrument(skip(ctx))]
 pub async fn delete_tag(
     ctx: Extension<ApiContext>,
     Query(params): Query<TagDeleteQueryParams>,
@@ -16,7 +16,8 @@ pub struct TagAssignmentPostQueryParams {
     booking_id: i64,
 }
 
-pub async fn post_tagassignment(
+#[tracing::instrument(skip(ctx))]
+pub async fn post_tagassignments(
     ctx: Extension<ApiContext>,
     Query(params): Query<TagAssignmentPostQueryParams>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -46,7 +47,8 @@ pub struct TagAssignmentGetQueryParams {
     tag_id: Option<i64>,
 }
 
-pub async fn get_tagassignment(
+#[tracing::instrument(skip(ctx))]
+pub async fn get_tagassignments(
     ctx: Extension<ApiContext>,
     Query(params): Query<TagAssignmentGetQueryParams>,
 ) -> Result<impl IntoResponse, AppError> {
@@ -70,3 +72,24 @@ pub async fn get_tagassignment(
         query_builder.build_query_as().fetch_all(&ctx.pool).await?;
     Ok(Json(tagassignments))
 }
+
+#[derive(Deserialize, Debug)]
+pub struct TagAssignmentDeleteQueryParams {
+    tag_id: i64,
+    booking_id: i64,
+}
+
+#[tracing::instrument(skip(ctx))]
+pub async fn delete_tagassignment(
+    ctx: Extension<ApiContext>,
+    Query(params): Query<TagAssignmentDeleteQueryParams>,
+) -> Result<impl IntoResponse, AppError> {
+    sqlx::query!(
+        "DELETE FROM tagassignment WHERE tgid = $1 AND bid = $2",
+        params.tag_id,
+        params.booking_id
+    )
+    .execute(&ctx.pool)
+    .await?;
+    Ok(())
+}
@@ -1,13 +1,29 @@
 use sqlx::sqlite::SqlitePoolOptions;
 use std::env;
+use tracing_subscriber::EnvFilter;
 
 mod booking;
 mod error;
 mod handlers;
+mod metrics;
+mod period;
+mod schedule;
 mod tag;
+mod tagassignment;
+mod timestamp;
+
+fn init_tracing() {
+    let filter = EnvFilter::try_from_env("EMGAUWA_LOG")
+        .or_else(|_| EnvFilter::try_from_default_env())
+        .unwrap_or_else(|_| EnvFilter::new("info"));
+
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+}
 
 #[tokio::main]
 async fn main() {
+    init_tracing();
+
     let pool = SqlitePoolOptions::new()
         .connect(
             &env::var("DATABASE_URL").expect("Failed to get environment variable DATABASE_URL"),
@@ -20,8 +36,16 @@ async fn main() {
         .await
         .expect("Failed to run migrations");
 
+    let single_active = env::var("SINGLE_ACTIVE_BOOKING")
+        .map(|value| value == "true")
+        .unwrap_or(false);
+
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
-    axum::serve(listener, handlers::router(pool).await.into_make_service())
-        .await
-        .unwrap();
+    tracing::info!("listening on 0.0.0.0:3000");
+    axum::serve(
+        listener,
+        handlers::router(pool, single_active).await.into_make_service(),
+    )
+    .await
+    .unwrap();
 }
@@ -0,0 +1,114 @@
+use std::sync::OnceLock;
+
+use prometheus::{
+    Encoder, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+/// Prometheus registry and handles shared across handlers. Cloning is cheap: every metric
+/// handle is internally reference-counted and backed by the same [`Registry`].
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    pub http_requests_total: IntCounterVec,
+    pub http_request_duration_seconds: HistogramVec,
+    pub db_errors_total: IntCounter,
+    pub pool_idle_connections: IntGauge,
+    pub pool_active_connections: IntGauge,
+    pub bookings_created_total: IntCounter,
+    pub tags_created_total: IntCounter,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "Total HTTP requests handled"),
+            &["method", "path", "status"],
+        )
+        .unwrap();
+
+        let http_request_duration_seconds = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds",
+            ),
+            &["method", "path"],
+        )
+        .unwrap();
+
+        let db_errors_total = IntCounter::new(
+            "db_errors_total",
+            "Total errors surfaced through AppError",
+        )
+        .unwrap();
+
+        let pool_idle_connections = IntGauge::new(
+            "sqlx_pool_idle_connections",
+            "Idle connections in the sqlx pool",
+        )
+        .unwrap();
+
+        let pool_active_connections = IntGauge::new(
+            "sqlx_pool_active_connections",
+            "Active (in-use) connections in the sqlx pool",
+        )
+        .unwrap();
+
+        let bookings_created_total =
+            IntCounter::new("bookings_created_total", "Total bookings created").unwrap();
+
+        let tags_created_total = IntCounter::new("tags_created_total", "Total tags created").unwrap();
+
+        registry
+            .register(Box::new(http_requests_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(http_request_duration_seconds.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(db_errors_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(pool_idle_connections.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(pool_active_connections.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(bookings_created_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(tags_created_total.clone()))
+            .unwrap();
+
+        Self {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            db_errors_total,
+            pool_idle_connections,
+            pool_active_connections,
+            bookings_created_total,
+            tags_created_total,
+        }
+    }
+
+    /// The process-wide registry. `ApiContext` holds a clone of this so handlers can bump
+    /// domain counters without reaching for a global directly.
+    pub fn global() -> &'static Metrics {
+        METRICS.get_or_init(Metrics::new)
+    }
+
+    /// Renders all registered metrics in Prometheus text exposition format.
+    pub fn encode(&self) -> String {
+        let metric_families = self.registry.gather();
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&metric_families, &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+}
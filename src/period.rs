@@ -0,0 +1,178 @@
+use std::borrow::Cow;
+
+use chrono::{NaiveTime, Timelike};
+use serde::de::Error as _;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Decode, Encode, Type};
+
+/// A single daily time-of-day window, e.g. 09:00-11:00.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl Period {
+    /// An overnight period (end at or before start) can't be materialized into a single day.
+    pub fn is_overnight(&self) -> bool {
+        self.end <= self.start
+    }
+}
+
+impl Serialize for Period {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Period", 2)?;
+        state.serialize_field("start", &self.start.format("%H:%M:%S").to_string())?;
+        state.serialize_field("end", &self.end.format("%H:%M:%S").to_string())?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Period {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            start: String,
+            end: String,
+        }
+        let raw = Raw::deserialize(deserializer)?;
+        Ok(Period {
+            start: parse_time(&raw.start).map_err(D::Error::custom)?,
+            end: parse_time(&raw.end).map_err(D::Error::custom)?,
+        })
+    }
+}
+
+fn parse_time(raw: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(raw, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(raw, "%H:%M"))
+        .map_err(|_| format!("'{raw}' is not a valid HH:MM[:SS] time"))
+}
+
+/// Parses the `start-end` shorthand accepted by the schedule query-param handlers.
+pub fn parse_period(raw: &str) -> Result<Period, String> {
+    let (start, end) = raw
+        .split_once('-')
+        .ok_or_else(|| format!("'{raw}' must be in the form HH:MM-HH:MM"))?;
+    Ok(Period {
+        start: parse_time(start)?,
+        end: parse_time(end)?,
+    })
+}
+
+/// A list of [`Period`]s stored as a single SQLite column. Each period is packed as its
+/// `start`/`end` second-of-day offsets (`<start_secs>-<end_secs>`), joined with `;`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Periods(pub Vec<Period>);
+
+impl Type<Sqlite> for Periods {
+    fn type_info() -> SqliteTypeInfo {
+        <String as Type<Sqlite>>::type_info()
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Periods {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'q>>) -> Result<IsNull, BoxDynError> {
+        let packed = self
+            .0
+            .iter()
+            .map(|period| {
+                format!(
+                    "{}-{}",
+                    period.start.num_seconds_from_midnight(),
+                    period.end.num_seconds_from_midnight()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(";");
+        buf.push(SqliteArgumentValue::Text(Cow::Owned(packed)));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Periods {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let raw = <&str as Decode<Sqlite>>::decode(value)?;
+        if raw.is_empty() {
+            return Ok(Periods(Vec::new()));
+        }
+
+        let periods = raw
+            .split(';')
+            .map(|chunk| {
+                let (start, end) = chunk
+                    .split_once('-')
+                    .ok_or("malformed period: missing '-' separator")?;
+                let start = start.parse::<u32>()?;
+                let end = end.parse::<u32>()?;
+                Ok(Period {
+                    start: NaiveTime::from_num_seconds_from_midnight_opt(start, 0)
+                        .ok_or("malformed period: start is out of range")?,
+                    end: NaiveTime::from_num_seconds_from_midnight_opt(end, 0)
+                        .ok_or("malformed period: end is out of range")?,
+                })
+            })
+            .collect::<Result<Vec<_>, BoxDynError>>()?;
+
+        Ok(Periods(periods))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn round_trip(periods: Periods) -> Periods {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let (decoded,): (Periods,) = sqlx::query_as("SELECT $1")
+            .bind(periods)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        decoded
+    }
+
+    #[tokio::test]
+    async fn round_trips_an_empty_list() {
+        let periods = Periods(Vec::new());
+
+        assert_eq!(round_trip(periods.clone()).await, periods);
+    }
+
+    #[tokio::test]
+    async fn round_trips_multiple_periods() {
+        let periods = Periods(vec![
+            Period {
+                start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(11, 30, 0).unwrap(),
+            },
+            Period {
+                start: NaiveTime::from_hms_opt(13, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(17, 15, 30).unwrap(),
+            },
+        ]);
+
+        assert_eq!(round_trip(periods.clone()).await, periods);
+    }
+
+    #[test]
+    fn period_parsing_accepts_both_hh_mm_and_hh_mm_ss() {
+        let short: Period = parse_period("09:00-11:30").unwrap();
+        assert_eq!(short.start, NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+        assert_eq!(short.end, NaiveTime::from_hms_opt(11, 30, 0).unwrap());
+
+        let long = parse_time("09:00:45").unwrap();
+        assert_eq!(long, NaiveTime::from_hms_opt(9, 0, 45).unwrap());
+    }
+}
@@ -0,0 +1,8 @@
+use crate::period::Periods;
+
+#[derive(Debug, serde::Serialize, sqlx::FromRow)]
+pub struct Schedule {
+    pub id: i64,
+    pub name: String,
+    pub periods: Periods,
+}
@@ -0,0 +1,6 @@
+// Tags can be added to a task for categorization and organisation
+#[derive(Debug, serde::Serialize, sqlx::FromRow, serde::Deserialize)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+}
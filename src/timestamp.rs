@@ -0,0 +1,130 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Decode, Encode, Type};
+
+/// A point in time. Accepted on input as an RFC 3339 string or, for backward compatibility, a
+/// bare epoch-millis integer; always serialized as RFC 3339; stored in SQLite as epoch millis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp(pub DateTime<Utc>);
+
+impl Timestamp {
+    pub fn now() -> Self {
+        Self(Utc::now())
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_rfc3339())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TimestampVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for TimestampVisitor {
+            type Value = Timestamp;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("an RFC 3339 timestamp or an epoch-millis integer")
+            }
+
+            // Query-string extractors (e.g. axum_extra::extract::Query) hand every value to
+            // serde as a string, so a bare-millis value like `startdate_min=1700000000000`
+            // arrives here rather than via visit_i64/visit_u64. Try millis first, then RFC 3339.
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                if let Ok(millis) = value.parse::<i64>() {
+                    return millis_to_timestamp(millis).map_err(E::custom);
+                }
+
+                DateTime::parse_from_rfc3339(value)
+                    .map(|dt| Timestamp(dt.with_timezone(&Utc)))
+                    .map_err(E::custom)
+            }
+
+            // JSON bodies can send a bare integer directly.
+            fn visit_i64<E: serde::de::Error>(self, millis: i64) -> Result<Self::Value, E> {
+                millis_to_timestamp(millis).map_err(E::custom)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, millis: u64) -> Result<Self::Value, E> {
+                self.visit_i64(millis as i64)
+            }
+        }
+
+        deserializer.deserialize_any(TimestampVisitor)
+    }
+}
+
+fn millis_to_timestamp(millis: i64) -> Result<Timestamp, String> {
+    Utc.timestamp_millis_opt(millis)
+        .single()
+        .map(Timestamp)
+        .ok_or_else(|| "timestamp out of range".to_string())
+}
+
+impl Type<Sqlite> for Timestamp {
+    fn type_info() -> SqliteTypeInfo {
+        <i64 as Type<Sqlite>>::type_info()
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Timestamp {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'q>>) -> Result<IsNull, BoxDynError> {
+        <i64 as Encode<Sqlite>>::encode_by_ref(&self.0.timestamp_millis(), buf)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Timestamp {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let millis = <i64 as Decode<Sqlite>>::decode(value)?;
+        Utc.timestamp_millis_opt(millis)
+            .single()
+            .map(Timestamp)
+            .ok_or_else(|| "timestamp out of range".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::value::{Error as ValueError, I64Deserializer, StringDeserializer};
+    use serde::de::IntoDeserializer;
+
+    // `axum_extra::extract::Query` hands query-string values to serde as plain strings, so a
+    // `startdate_min=<millis>` query param is deserialized the same way as this bare numeric
+    // string -- proving it still resolves to the right instant rather than failing RFC 3339
+    // parsing.
+    #[test]
+    fn deserializes_bare_millis_string_from_query_params() {
+        let deserializer: StringDeserializer<ValueError> =
+            "1700000000000".to_string().into_deserializer();
+
+        let timestamp = Timestamp::deserialize(deserializer).unwrap();
+
+        assert_eq!(timestamp.0.timestamp_millis(), 1700000000000);
+    }
+
+    #[test]
+    fn deserializes_rfc3339_string() {
+        let deserializer: StringDeserializer<ValueError> =
+            "2023-11-14T22:13:20+00:00".to_string().into_deserializer();
+
+        let timestamp = Timestamp::deserialize(deserializer).unwrap();
+
+        assert_eq!(timestamp.0.timestamp_millis(), 1700000000000);
+    }
+
+    #[test]
+    fn deserializes_bare_integer_from_json_bodies() {
+        let deserializer: I64Deserializer<ValueError> = 1700000000000i64.into_deserializer();
+
+        let timestamp = Timestamp::deserialize(deserializer).unwrap();
+
+        assert_eq!(timestamp.0.timestamp_millis(), 1700000000000);
+    }
+}